@@ -0,0 +1,71 @@
+use query_source::{Insertable, Queriable, QuerySource, Table};
+use result::QueryResult;
+use row::Cursor;
+use types::{NativeSqlType, ToSql};
+use super::RawResult;
+
+/// The query surface shared by `Connection` and `Transaction`. Generic
+/// code written as `fn load_users<E: Executor>(e: &E)` runs identically
+/// against a bare connection or one borrowed inside
+/// `Connection::transaction`.
+pub trait Executor {
+    /// Runs `query` and discards any rows it returns, yielding the number
+    /// of rows affected.
+    fn execute(&self, query: &str) -> QueryResult<usize>;
+
+    /// Like `execute`, but binds `params` out-of-band rather than
+    /// interpolating them into the SQL text.
+    fn execute_params(&self, query: &str, params: &[&ToSql]) -> QueryResult<usize>;
+
+    /// Runs `source` and returns a `Cursor` that lazily decodes each row
+    /// it returns as a `T`.
+    fn query_all<U, T>(&self, source: &U) -> QueryResult<Cursor<RawResult, U::SqlType, T>>
+    where
+        U: QuerySource,
+        T: Queriable<U::SqlType>;
+
+    /// Like `query_all`, but only returns the first row (if any).
+    fn query_one<U, T>(&self, source: &U) -> QueryResult<Option<T>>
+    where
+        U: QuerySource,
+        T: Queriable<U::SqlType>;
+
+    /// Like `query_all`, for a hand-written parameterized `query` rather
+    /// than a `QuerySource`.
+    fn query_all_params<ST, T>(
+        &self,
+        query: &str,
+        params: &[&ToSql],
+    ) -> QueryResult<Cursor<RawResult, ST, T>>
+    where
+        ST: NativeSqlType,
+        T: Queriable<ST>;
+
+    /// Runs a type-checked `INSERT INTO` for a single `record`.
+    fn insert<T, R>(&self, table: &T, record: &R) -> QueryResult<usize>
+    where
+        T: Table,
+        R: Insertable<T>;
+
+    /// Like `insert`, for several `records` in a single statement.
+    fn insert_all<T, R>(&self, table: &T, records: &[R]) -> QueryResult<usize>
+    where
+        T: Table,
+        R: Insertable<T>;
+
+    /// Like `insert`, but appends `RETURNING *` and decodes the inserted
+    /// row back into an `Out`.
+    fn insert_returning<T, R, Out>(&self, table: &T, record: &R) -> QueryResult<Vec<Out>>
+    where
+        T: Table,
+        R: Insertable<T>,
+        Out: Queriable<T::SqlType>;
+
+    /// Like `insert_all`, but appends `RETURNING *` and decodes every
+    /// inserted row back into an `Out`.
+    fn insert_all_returning<T, R, Out>(&self, table: &T, records: &[R]) -> QueryResult<Vec<Out>>
+    where
+        T: Table,
+        R: Insertable<T>,
+        Out: Queriable<T::SqlType>;
+}
@@ -0,0 +1,267 @@
+mod executor;
+mod raw;
+mod statement_cache;
+mod transaction;
+
+use query_source::{Insertable, Queriable, QuerySource, Table};
+use result::{ConnectionResult, QueryResult};
+use row::Cursor;
+use types::{NativeSqlType, ToSql};
+
+pub use self::executor::Executor;
+pub use self::raw::RawResult;
+pub use self::transaction::Transaction;
+
+/// A single connection to a Postgres database.
+pub struct Connection {
+    raw_connection: raw::RawConnection,
+}
+
+impl Connection {
+    pub fn establish(database_url: &str) -> ConnectionResult<Self> {
+        raw::RawConnection::establish(database_url)
+            .map(|raw_connection| Connection { raw_connection: raw_connection })
+    }
+
+    /// Runs `query` and discards any rows it returns, yielding the number
+    /// of rows affected. Intended for DDL/DML that doesn't go through the
+    /// query builder, e.g. `CREATE TABLE` in test setup.
+    pub fn execute(&self, query: &str) -> QueryResult<usize> {
+        self.raw_connection.execute(query).map(|result| result.rows_affected())
+    }
+
+    /// Like `execute`, but binds `params` out-of-band via libpq's
+    /// parameterized query path (`$1, $2, ...` in `query`) instead of
+    /// interpolating them into the SQL text.
+    pub fn execute_params(&self, query: &str, params: &[&ToSql]) -> QueryResult<usize> {
+        self.raw_connection.execute_params(query, params).map(|result| result.rows_affected())
+    }
+
+    /// Sets how many distinct query strings' prepared statements this
+    /// connection keeps around. A miss transparently `PREPARE`s (and
+    /// caches) the query's plan; a hit reuses it via `PQexecPrepared`.
+    /// `0` disables the cache, restoring a fresh `PQexecParams` per call.
+    pub fn set_statement_cache_capacity(&self, capacity: usize) -> QueryResult<()> {
+        self.raw_connection.set_statement_cache_capacity(capacity)
+    }
+
+    /// How many `PREPARE` round-trips this connection has made. Mainly
+    /// useful for tests asserting the statement cache is actually being
+    /// hit rather than re-preparing every call.
+    pub fn prepare_count(&self) -> usize {
+        self.raw_connection.prepare_count()
+    }
+
+    /// Runs `source` and returns a `Cursor` that lazily decodes each row
+    /// it returns as a `T`, rather than collecting them all up front.
+    pub fn query_all<U, T>(&self, source: &U) -> QueryResult<Cursor<RawResult, U::SqlType, T>>
+    where
+        U: QuerySource,
+        T: Queriable<U::SqlType>,
+    {
+        let mut binds = Vec::new();
+        let sql = source.to_sql(&mut binds);
+        self.query_all_params::<U::SqlType, T>(&sql, &binds)
+    }
+
+    /// Like `query_all`, but only returns the first row (if any).
+    pub fn query_one<U, T>(&self, source: &U) -> QueryResult<Option<T>>
+    where
+        U: QuerySource,
+        T: Queriable<U::SqlType>,
+    {
+        let cursor = try!(self.query_all(source));
+        match cursor.first() {
+            Some(row) => row.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Like `query_all`, for a hand-written parameterized `query` rather
+    /// than a `QuerySource`. The caller is trusted to supply an `ST` that
+    /// matches what `query` actually returns, the same trust `select_sql`
+    /// asks for.
+    pub fn query_all_params<ST, T>(
+        &self,
+        query: &str,
+        params: &[&ToSql],
+    ) -> QueryResult<Cursor<RawResult, ST, T>>
+    where
+        ST: NativeSqlType,
+        T: Queriable<ST>,
+    {
+        let result = try!(self.raw_connection.execute_params(query, params));
+        Ok(Cursor::new(result))
+    }
+
+    /// Runs a type-checked `INSERT INTO` for a single `record`, yielding
+    /// the number of rows affected (always `1` on success).
+    pub fn insert<T, R>(&self, table: &T, record: &R) -> QueryResult<usize>
+    where
+        T: Table,
+        R: Insertable<T>,
+    {
+        let sql = insert_sql::<T, R>(table, 1);
+        self.execute_params(&sql, &record.values())
+    }
+
+    /// Like `insert`, for several `records` in a single statement.
+    pub fn insert_all<T, R>(&self, table: &T, records: &[R]) -> QueryResult<usize>
+    where
+        T: Table,
+        R: Insertable<T>,
+    {
+        if records.is_empty() {
+            return Ok(0);
+        }
+        let sql = insert_sql::<T, R>(table, records.len());
+        let values: Vec<_> = records.iter().flat_map(|record| record.values()).collect();
+        self.execute_params(&sql, &values)
+    }
+
+    /// Like `insert`, but appends `RETURNING *` and decodes the inserted
+    /// row straight back into a `T::SqlType`-shaped `Out`.
+    pub fn insert_returning<T, R, Out>(&self, table: &T, record: &R) -> QueryResult<Vec<Out>>
+    where
+        T: Table,
+        R: Insertable<T>,
+        Out: Queriable<T::SqlType>,
+    {
+        let sql = format!("{} RETURNING *", insert_sql::<T, R>(table, 1));
+        let cursor = try!(self.query_all_params::<T::SqlType, Out>(&sql, &record.values()));
+        cursor.collect::<QueryResult<Vec<_>>>()
+    }
+
+    /// Like `insert_all`, but appends `RETURNING *` and decodes every
+    /// inserted row back into an `Out`.
+    pub fn insert_all_returning<T, R, Out>(&self, table: &T, records: &[R]) -> QueryResult<Vec<Out>>
+    where
+        T: Table,
+        R: Insertable<T>,
+        Out: Queriable<T::SqlType>,
+    {
+        if records.is_empty() {
+            return Ok(Vec::new());
+        }
+        let sql = format!("{} RETURNING *", insert_sql::<T, R>(table, records.len()));
+        let values: Vec<_> = records.iter().flat_map(|record| record.values()).collect();
+        let cursor = try!(self.query_all_params::<T::SqlType, Out>(&sql, &values));
+        cursor.collect::<QueryResult<Vec<_>>>()
+    }
+
+    /// Runs `f` inside a `BEGIN`/`COMMIT` block, passing it a `Transaction`
+    /// that implements `Executor` the same way this connection does.
+    /// Rolls back instead of committing if `f` returns `Err`, and also if
+    /// `f` panics -- unwinding drops the `Transaction` before the panic
+    /// continues, and `Transaction::drop` rolls back anything that hasn't
+    /// committed yet.
+    pub fn transaction<F, T>(&self, f: F) -> QueryResult<T>
+    where
+        F: FnOnce(&Transaction) -> QueryResult<T>,
+    {
+        try!(self.execute("BEGIN"));
+        let tx = Transaction::new(self);
+        let result = f(&tx);
+        if result.is_ok() {
+            try!(self.execute("COMMIT"));
+            tx.commit();
+        }
+        result
+    }
+}
+
+impl Executor for Connection {
+    fn execute(&self, query: &str) -> QueryResult<usize> {
+        self.execute(query)
+    }
+
+    fn execute_params(&self, query: &str, params: &[&ToSql]) -> QueryResult<usize> {
+        self.execute_params(query, params)
+    }
+
+    fn query_all<U, T>(&self, source: &U) -> QueryResult<Cursor<RawResult, U::SqlType, T>>
+    where
+        U: QuerySource,
+        T: Queriable<U::SqlType>,
+    {
+        self.query_all(source)
+    }
+
+    fn query_one<U, T>(&self, source: &U) -> QueryResult<Option<T>>
+    where
+        U: QuerySource,
+        T: Queriable<U::SqlType>,
+    {
+        self.query_one(source)
+    }
+
+    fn query_all_params<ST, T>(
+        &self,
+        query: &str,
+        params: &[&ToSql],
+    ) -> QueryResult<Cursor<RawResult, ST, T>>
+    where
+        ST: NativeSqlType,
+        T: Queriable<ST>,
+    {
+        self.query_all_params(query, params)
+    }
+
+    fn insert<T, R>(&self, table: &T, record: &R) -> QueryResult<usize>
+    where
+        T: Table,
+        R: Insertable<T>,
+    {
+        self.insert(table, record)
+    }
+
+    fn insert_all<T, R>(&self, table: &T, records: &[R]) -> QueryResult<usize>
+    where
+        T: Table,
+        R: Insertable<T>,
+    {
+        self.insert_all(table, records)
+    }
+
+    fn insert_returning<T, R, Out>(&self, table: &T, record: &R) -> QueryResult<Vec<Out>>
+    where
+        T: Table,
+        R: Insertable<T>,
+        Out: Queriable<T::SqlType>,
+    {
+        self.insert_returning(table, record)
+    }
+
+    fn insert_all_returning<T, R, Out>(&self, table: &T, records: &[R]) -> QueryResult<Vec<Out>>
+    where
+        T: Table,
+        R: Insertable<T>,
+        Out: Queriable<T::SqlType>,
+    {
+        self.insert_all_returning(table, records)
+    }
+}
+
+/// Renders `INSERT INTO <table> (<columns>) VALUES (...), (...)`, with one
+/// parenthesized group of `$n` placeholders per row of `row_count`.
+fn insert_sql<T, R>(table: &T, row_count: usize) -> String
+where
+    T: Table,
+    R: Insertable<T>,
+{
+    let columns = R::column_names();
+    let rows_sql: Vec<String> = (0..row_count)
+        .map(|row_index| {
+            let placeholders: Vec<String> = (0..columns.len())
+                .map(|column_index| format!("${}", row_index * columns.len() + column_index + 1))
+                .collect();
+            format!("({})", placeholders.join(", "))
+        })
+        .collect();
+    format!(
+        "INSERT INTO {} ({}) VALUES {}",
+        table.name(),
+        columns.join(", "),
+        rows_sql.join(", "),
+    )
+}
@@ -0,0 +1,282 @@
+use std::cell::{Cell, RefCell};
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+
+use result::{ConnectionError, ConnectionResult, Error, QueryResult};
+use row::{RawRow, RowSource};
+use types::ToSql;
+use super::statement_cache::StatementCache;
+
+/// How many prepared statements a fresh `Connection` caches before this
+/// chunk's `set_statement_cache_capacity` is ever called.
+const DEFAULT_STATEMENT_CACHE_CAPACITY: usize = 100;
+
+#[allow(non_camel_case_types)]
+enum PGconn {}
+#[allow(non_camel_case_types)]
+enum PGresult {}
+
+const CONNECTION_OK: c_int = 0;
+const PGRES_COMMAND_OK: c_int = 1;
+const PGRES_TUPLES_OK: c_int = 2;
+
+#[link(name = "pq")]
+extern "C" {
+    fn PQconnectdb(conninfo: *const c_char) -> *mut PGconn;
+    fn PQstatus(conn: *const PGconn) -> c_int;
+    fn PQerrorMessage(conn: *const PGconn) -> *const c_char;
+    fn PQfinish(conn: *mut PGconn);
+    fn PQexec(conn: *mut PGconn, query: *const c_char) -> *mut PGresult;
+    fn PQexecParams(
+        conn: *mut PGconn,
+        command: *const c_char,
+        n_params: c_int,
+        param_types: *const u32,
+        param_values: *const *const c_char,
+        param_lengths: *const c_int,
+        param_formats: *const c_int,
+        result_format: c_int,
+    ) -> *mut PGresult;
+    fn PQprepare(
+        conn: *mut PGconn,
+        stmt_name: *const c_char,
+        query: *const c_char,
+        n_params: c_int,
+        param_types: *const u32,
+    ) -> *mut PGresult;
+    fn PQexecPrepared(
+        conn: *mut PGconn,
+        stmt_name: *const c_char,
+        n_params: c_int,
+        param_values: *const *const c_char,
+        param_lengths: *const c_int,
+        param_formats: *const c_int,
+        result_format: c_int,
+    ) -> *mut PGresult;
+    fn PQresultStatus(res: *const PGresult) -> c_int;
+    fn PQntuples(res: *const PGresult) -> c_int;
+    fn PQnfields(res: *const PGresult) -> c_int;
+    fn PQgetvalue(res: *const PGresult, row: c_int, col: c_int) -> *const c_char;
+    fn PQgetlength(res: *const PGresult, row: c_int, col: c_int) -> c_int;
+    fn PQgetisnull(res: *const PGresult, row: c_int, col: c_int) -> c_int;
+    fn PQcmdTuples(res: *const PGresult) -> *const c_char;
+    fn PQclear(res: *mut PGresult);
+}
+
+/// A thin wrapper around a raw libpq connection. `Connection` is the
+/// public, type-safe API -- this just owns the FFI handle and frees it on
+/// drop.
+pub struct RawConnection {
+    internal_connection: *mut PGconn,
+    statement_cache: RefCell<StatementCache>,
+    prepare_count: Cell<usize>,
+}
+
+impl RawConnection {
+    pub fn establish(database_url: &str) -> ConnectionResult<Self> {
+        let connection_string = CString::new(database_url).unwrap();
+        let connection_ptr = unsafe { PQconnectdb(connection_string.as_ptr()) };
+        let status = unsafe { PQstatus(connection_ptr) };
+        if status == CONNECTION_OK {
+            Ok(RawConnection {
+                internal_connection: connection_ptr,
+                statement_cache: RefCell::new(StatementCache::new(DEFAULT_STATEMENT_CACHE_CAPACITY)),
+                prepare_count: Cell::new(0),
+            })
+        } else {
+            let message = last_error_message(connection_ptr);
+            unsafe { PQfinish(connection_ptr) };
+            Err(ConnectionError::BadConnection(message))
+        }
+    }
+
+    pub fn execute(&self, query: &str) -> QueryResult<RawResult> {
+        let query_c = CString::new(query).unwrap();
+        let result_ptr = unsafe { PQexec(self.internal_connection, query_c.as_ptr()) };
+        RawResult::new(result_ptr, self.internal_connection)
+    }
+
+    /// Like `execute`, binding `params` via libpq's parameterized path
+    /// instead of interpolating them into `query`. When the statement
+    /// cache is enabled, `query` is prepared at most once and subsequent
+    /// calls with the same text reuse the cached plan via
+    /// `PQexecPrepared`; a capacity of `0` restores plain `PQexecParams`.
+    pub fn execute_params(&self, query: &str, params: &[&ToSql]) -> QueryResult<RawResult> {
+        // `_param_c_strings` isn't read again, but it has to outlive
+        // `param_values`' raw pointers below -- `build_params` ties both
+        // together in one return so that isn't easy to get wrong.
+        let (_param_c_strings, param_values) = try!(self.build_params(params));
+
+        if self.statement_cache.borrow().capacity() == 0 {
+            return self.exec_params(query, &param_values);
+        }
+
+        let cached_name = self.statement_cache.borrow_mut().get(query);
+        let stmt_name = match cached_name {
+            Some(name) => name,
+            None => {
+                let (name, evicted) = self.statement_cache.borrow_mut().insert(query);
+                for old_name in evicted {
+                    try!(self.deallocate(&old_name));
+                }
+                try!(self.prepare(&name, query));
+                name
+            }
+        };
+        self.exec_prepared(&stmt_name, &param_values)
+    }
+
+    /// Replaces the statement cache's capacity, `DEALLOCATE`ing whatever
+    /// no longer fits. `0` disables caching entirely.
+    pub fn set_statement_cache_capacity(&self, capacity: usize) -> QueryResult<()> {
+        let evicted = self.statement_cache.borrow_mut().set_capacity(capacity);
+        for name in evicted {
+            try!(self.deallocate(&name));
+        }
+        Ok(())
+    }
+
+    /// The number of `PREPARE` round-trips this connection has made, for
+    /// tests to assert the statement cache is actually being hit.
+    pub fn prepare_count(&self) -> usize {
+        self.prepare_count.get()
+    }
+
+    /// Builds the owned/borrowed `CString`s (and the raw pointers libpq
+    /// wants) for a set of bound parameters. The `CString`s must outlive
+    /// the pointers, so both are returned together.
+    fn build_params(&self, params: &[&ToSql]) -> QueryResult<(Vec<Option<CString>>, Vec<*const c_char>)> {
+        let mut param_c_strings: Vec<Option<CString>> = Vec::with_capacity(params.len());
+        for param in params {
+            let c_string = match param.to_sql().as_bytes() {
+                // Postgres' text format can't represent an embedded NUL
+                // byte in a bound value either, so surface it as a query
+                // error instead of panicking on attacker-controlled data.
+                Some(bytes) => Some(try!(
+                    CString::new(bytes).map_err(|e| Error::QueryBuilderError(Box::new(e)))
+                )),
+                None => None,
+            };
+            param_c_strings.push(c_string);
+        }
+        let param_values: Vec<*const c_char> = param_c_strings.iter()
+            .map(|c_string| c_string.as_ref().map(|c| c.as_ptr()).unwrap_or(ptr::null()))
+            .collect();
+        Ok((param_c_strings, param_values))
+    }
+
+    fn exec_params(&self, query: &str, param_values: &[*const c_char]) -> QueryResult<RawResult> {
+        let query_c = CString::new(query).unwrap();
+        let result_ptr = unsafe {
+            PQexecParams(
+                self.internal_connection,
+                query_c.as_ptr(),
+                param_values.len() as c_int,
+                ptr::null(),
+                param_values.as_ptr(),
+                ptr::null(),
+                ptr::null(),
+                0,
+            )
+        };
+        RawResult::new(result_ptr, self.internal_connection)
+    }
+
+    fn exec_prepared(&self, stmt_name: &str, param_values: &[*const c_char]) -> QueryResult<RawResult> {
+        let name_c = CString::new(stmt_name).unwrap();
+        let result_ptr = unsafe {
+            PQexecPrepared(
+                self.internal_connection,
+                name_c.as_ptr(),
+                param_values.len() as c_int,
+                param_values.as_ptr(),
+                ptr::null(),
+                ptr::null(),
+                0,
+            )
+        };
+        RawResult::new(result_ptr, self.internal_connection)
+    }
+
+    fn prepare(&self, stmt_name: &str, query: &str) -> QueryResult<()> {
+        let name_c = CString::new(stmt_name).unwrap();
+        let query_c = CString::new(query).unwrap();
+        let result_ptr = unsafe {
+            PQprepare(self.internal_connection, name_c.as_ptr(), query_c.as_ptr(), 0, ptr::null())
+        };
+        self.prepare_count.set(self.prepare_count.get() + 1);
+        RawResult::new(result_ptr, self.internal_connection).map(|_| ())
+    }
+
+    fn deallocate(&self, stmt_name: &str) -> QueryResult<()> {
+        self.execute(&format!("DEALLOCATE {}", stmt_name)).map(|_| ())
+    }
+}
+
+impl Drop for RawConnection {
+    fn drop(&mut self) {
+        unsafe { PQfinish(self.internal_connection) }
+    }
+}
+
+fn last_error_message(conn: *const PGconn) -> String {
+    unsafe { CStr::from_ptr(PQerrorMessage(conn)).to_string_lossy().into_owned() }
+}
+
+/// The result of a single `PQexec`, `PQexecParams`, `PQexecPrepared` or
+/// `PQprepare` call.
+pub struct RawResult {
+    internal_result: *mut PGresult,
+}
+
+impl RawResult {
+    fn new(ptr: *mut PGresult, conn: *const PGconn) -> QueryResult<Self> {
+        let status = unsafe { PQresultStatus(ptr) };
+        if status == PGRES_COMMAND_OK || status == PGRES_TUPLES_OK {
+            Ok(RawResult { internal_result: ptr })
+        } else {
+            let message = last_error_message(conn);
+            unsafe { PQclear(ptr) };
+            Err(Error::DatabaseError(message))
+        }
+    }
+
+    pub fn rows_affected(&self) -> usize {
+        let tuples_ptr = unsafe { PQcmdTuples(self.internal_result) };
+        let tuples_str = unsafe { CStr::from_ptr(tuples_ptr).to_string_lossy() };
+        tuples_str.parse().unwrap_or(0)
+    }
+
+    fn column_value(&self, row_idx: c_int, col_idx: c_int) -> Option<Vec<u8>> {
+        let is_null = unsafe { PQgetisnull(self.internal_result, row_idx, col_idx) } != 0;
+        if is_null {
+            return None;
+        }
+        let value_ptr = unsafe { PQgetvalue(self.internal_result, row_idx, col_idx) };
+        let length = unsafe { PQgetlength(self.internal_result, row_idx, col_idx) } as usize;
+        let bytes = unsafe { ::std::slice::from_raw_parts(value_ptr as *const u8, length) };
+        Some(bytes.to_vec())
+    }
+}
+
+impl Drop for RawResult {
+    fn drop(&mut self) {
+        unsafe { PQclear(self.internal_result) }
+    }
+}
+
+impl RowSource for RawResult {
+    fn row_count(&self) -> usize {
+        unsafe { PQntuples(self.internal_result) as usize }
+    }
+
+    fn get_row(&self, index: usize) -> RawRow {
+        let row_idx = index as c_int;
+        let col_count = unsafe { PQnfields(self.internal_result) };
+        let columns = (0..col_count)
+            .map(|col_idx| self.column_value(row_idx, col_idx))
+            .collect();
+        RawRow::new(columns)
+    }
+}
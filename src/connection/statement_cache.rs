@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+
+/// Maps generated SQL text to the name of the server-side prepared
+/// statement holding its plan. Since a `QuerySource` always produces the
+/// same SQL text for the same query shape, that text alone is a stable
+/// cache key -- no separate fingerprinting is needed.
+///
+/// Eviction is a plain LRU: when a miss would push the cache over
+/// `capacity`, the least-recently-used entry is dropped. The cache itself
+/// never talks to Postgres -- `insert`/`set_capacity` just tell the caller
+/// which statement names now need a server-side `DEALLOCATE`.
+pub struct StatementCache {
+    capacity: usize,
+    next_id: usize,
+    names: HashMap<String, String>,
+    // Recency order, least-recently-used first.
+    order: Vec<String>,
+}
+
+impl StatementCache {
+    pub fn new(capacity: usize) -> Self {
+        StatementCache {
+            capacity: capacity,
+            next_id: 0,
+            names: HashMap::new(),
+            order: Vec::new(),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Changes the cache's capacity, returning the statement names evicted
+    /// to bring the cache back within it (e.g. everything, if set to `0`).
+    pub fn set_capacity(&mut self, capacity: usize) -> Vec<String> {
+        self.capacity = capacity;
+        self.evict_overflow()
+    }
+
+    /// Looks up `sql`, marking it most-recently-used if present.
+    pub fn get(&mut self, sql: &str) -> Option<String> {
+        let name = self.names.get(sql).cloned();
+        if name.is_some() {
+            self.touch(sql);
+        }
+        name
+    }
+
+    /// Reserves a fresh statement name for `sql`, returning it alongside
+    /// any statement names evicted to make room. The caller is
+    /// responsible for actually issuing the `PREPARE`/`DEALLOCATE`s.
+    pub fn insert(&mut self, sql: &str) -> (String, Vec<String>) {
+        let name = format!("__diesel_stmt_{}", self.next_id);
+        self.next_id += 1;
+        self.names.insert(sql.to_string(), name.clone());
+        self.order.push(sql.to_string());
+        (name, self.evict_overflow())
+    }
+
+    fn touch(&mut self, sql: &str) {
+        if let Some(position) = self.order.iter().position(|cached| cached == sql) {
+            let sql = self.order.remove(position);
+            self.order.push(sql);
+        }
+    }
+
+    fn evict_overflow(&mut self) -> Vec<String> {
+        let mut evicted = Vec::new();
+        while self.order.len() > self.capacity {
+            let oldest = self.order.remove(0);
+            if let Some(name) = self.names.remove(&oldest) {
+                evicted.push(name);
+            }
+        }
+        evicted
+    }
+}
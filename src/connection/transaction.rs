@@ -0,0 +1,109 @@
+use std::cell::Cell;
+
+use query_source::{Insertable, Queriable, QuerySource, Table};
+use result::QueryResult;
+use row::Cursor;
+use types::{NativeSqlType, ToSql};
+use super::{Connection, Executor, RawResult};
+
+/// An in-progress `BEGIN`/`COMMIT`/`ROLLBACK` block, obtained from
+/// `Connection::transaction`. Implements `Executor` by forwarding to the
+/// `Connection` it borrows, so the same query calls work unchanged inside
+/// one. Rolls back on `Drop` unless the transaction committed first --
+/// which is also what rolls it back when the closure that owns it panics.
+pub struct Transaction<'conn> {
+    connection: &'conn Connection,
+    committed: Cell<bool>,
+}
+
+impl<'conn> Transaction<'conn> {
+    pub fn new(connection: &'conn Connection) -> Self {
+        Transaction { connection: connection, committed: Cell::new(false) }
+    }
+
+    pub fn commit(&self) {
+        self.committed.set(true);
+    }
+}
+
+impl<'conn> Executor for Transaction<'conn> {
+    fn execute(&self, query: &str) -> QueryResult<usize> {
+        self.connection.execute(query)
+    }
+
+    fn execute_params(&self, query: &str, params: &[&ToSql]) -> QueryResult<usize> {
+        self.connection.execute_params(query, params)
+    }
+
+    fn query_all<U, T>(&self, source: &U) -> QueryResult<Cursor<RawResult, U::SqlType, T>>
+    where
+        U: QuerySource,
+        T: Queriable<U::SqlType>,
+    {
+        self.connection.query_all(source)
+    }
+
+    fn query_one<U, T>(&self, source: &U) -> QueryResult<Option<T>>
+    where
+        U: QuerySource,
+        T: Queriable<U::SqlType>,
+    {
+        self.connection.query_one(source)
+    }
+
+    fn query_all_params<ST, T>(
+        &self,
+        query: &str,
+        params: &[&ToSql],
+    ) -> QueryResult<Cursor<RawResult, ST, T>>
+    where
+        ST: NativeSqlType,
+        T: Queriable<ST>,
+    {
+        self.connection.query_all_params(query, params)
+    }
+
+    fn insert<T, R>(&self, table: &T, record: &R) -> QueryResult<usize>
+    where
+        T: Table,
+        R: Insertable<T>,
+    {
+        self.connection.insert(table, record)
+    }
+
+    fn insert_all<T, R>(&self, table: &T, records: &[R]) -> QueryResult<usize>
+    where
+        T: Table,
+        R: Insertable<T>,
+    {
+        self.connection.insert_all(table, records)
+    }
+
+    fn insert_returning<T, R, Out>(&self, table: &T, record: &R) -> QueryResult<Vec<Out>>
+    where
+        T: Table,
+        R: Insertable<T>,
+        Out: Queriable<T::SqlType>,
+    {
+        self.connection.insert_returning(table, record)
+    }
+
+    fn insert_all_returning<T, R, Out>(&self, table: &T, records: &[R]) -> QueryResult<Vec<Out>>
+    where
+        T: Table,
+        R: Insertable<T>,
+        Out: Queriable<T::SqlType>,
+    {
+        self.connection.insert_all_returning(table, records)
+    }
+}
+
+impl<'conn> Drop for Transaction<'conn> {
+    fn drop(&mut self) {
+        if !self.committed.get() {
+            // Best-effort: there's no way to surface a failed `ROLLBACK`
+            // to the caller from inside `Drop`.
+            let _ = self.connection.execute("ROLLBACK");
+        }
+    }
+}
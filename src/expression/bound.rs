@@ -0,0 +1,77 @@
+use std::marker::PhantomData;
+
+use types::{BigInt, Bool, Integer, Nullable, NativeSqlType, SmallInt, ToSql, VarChar};
+use super::Expression;
+
+/// A literal value used on one side of a comparison, e.g. the `18` in
+/// `age.gt(18)`. `ST` is looked up by `AsExpression`, not supplied by the
+/// caller -- that's what lets `age.gt(18)` type check while `age.eq("foo")`
+/// doesn't.
+pub struct Bound<ST, T> {
+    item: T,
+    _marker: PhantomData<ST>,
+}
+
+impl<ST, T> Bound<ST, T> {
+    pub fn new(item: T) -> Self {
+        Bound { item: item, _marker: PhantomData }
+    }
+}
+
+impl<ST: NativeSqlType, T: ToSql> Expression for Bound<ST, T> {
+    type SqlType = ST;
+
+    fn to_sql<'a>(&'a self, binds: &mut Vec<&'a ToSql>) -> String {
+        binds.push(&self.item);
+        format!("${}", binds.len())
+    }
+}
+
+/// Picks the `NativeSqlType` a bare Rust value should be compared as, so
+/// `some_column.eq(some_literal)` can be checked the same way
+/// `some_column.eq(other_column)` is.
+pub trait AsExpression<ST: NativeSqlType> {
+    type Expression: Expression<SqlType = ST>;
+
+    fn as_expression(self) -> Self::Expression;
+}
+
+macro_rules! as_expression {
+    ($ty:ty, $sql_type:ty) => {
+        impl AsExpression<$sql_type> for $ty {
+            type Expression = Bound<$sql_type, $ty>;
+
+            fn as_expression(self) -> Self::Expression {
+                Bound::new(self)
+            }
+        }
+    }
+}
+
+as_expression!(i16, SmallInt);
+as_expression!(i32, Integer);
+as_expression!(i64, BigInt);
+as_expression!(bool, Bool);
+as_expression!(String, VarChar);
+
+impl<'a> AsExpression<VarChar> for &'a str {
+    type Expression = Bound<VarChar, String>;
+
+    fn as_expression(self) -> Self::Expression {
+        Bound::new(self.to_string())
+    }
+}
+
+/// A bare value can always stand in for a nullable column of the matching
+/// type (it's just never `NULL`).
+impl<ST, T> AsExpression<Nullable<ST>> for T
+where
+    ST: NativeSqlType,
+    T: AsExpression<ST> + ToSql,
+{
+    type Expression = Bound<Nullable<ST>, T>;
+
+    fn as_expression(self) -> Self::Expression {
+        Bound::new(self)
+    }
+}
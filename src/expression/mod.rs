@@ -0,0 +1,62 @@
+//! Typed SQL expressions: columns, bound literals, and the predicates
+//! built by comparing them. `QuerySource::filter` only accepts an `Expression`
+//! whose `SqlType` is `Bool`, which is what makes a `WHERE` clause
+//! statically checked the same way `select` already is.
+use types::{NativeSqlType, ToSql};
+
+mod bound;
+mod predicates;
+
+pub use self::bound::{AsExpression, Bound};
+pub use self::predicates::Predicate;
+
+pub trait Expression {
+    type SqlType: NativeSqlType;
+
+    /// Renders this expression's SQL text, pushing any literal operands
+    /// onto `binds` as `$1, $2, ...` placeholders rather than formatting
+    /// them into the returned string.
+    fn to_sql<'a>(&'a self, binds: &mut Vec<&'a ToSql>) -> String;
+
+    fn eq<T: AsExpression<Self::SqlType>>(self, other: T) -> Predicate<Self, T::Expression>
+    where
+        Self: Sized,
+    {
+        Predicate::new(self, other.as_expression(), "=")
+    }
+
+    fn ne<T: AsExpression<Self::SqlType>>(self, other: T) -> Predicate<Self, T::Expression>
+    where
+        Self: Sized,
+    {
+        Predicate::new(self, other.as_expression(), "!=")
+    }
+
+    fn gt<T: AsExpression<Self::SqlType>>(self, other: T) -> Predicate<Self, T::Expression>
+    where
+        Self: Sized,
+    {
+        Predicate::new(self, other.as_expression(), ">")
+    }
+
+    fn lt<T: AsExpression<Self::SqlType>>(self, other: T) -> Predicate<Self, T::Expression>
+    where
+        Self: Sized,
+    {
+        Predicate::new(self, other.as_expression(), "<")
+    }
+
+    fn ge<T: AsExpression<Self::SqlType>>(self, other: T) -> Predicate<Self, T::Expression>
+    where
+        Self: Sized,
+    {
+        Predicate::new(self, other.as_expression(), ">=")
+    }
+
+    fn le<T: AsExpression<Self::SqlType>>(self, other: T) -> Predicate<Self, T::Expression>
+    where
+        Self: Sized,
+    {
+        Predicate::new(self, other.as_expression(), "<=")
+    }
+}
@@ -0,0 +1,29 @@
+use types::{Bool, ToSql};
+use super::Expression;
+
+/// The result of comparing two expressions of the same `SqlType`, e.g.
+/// `age.gt(18)`. Always a `Bool` expression, which is why it's exactly
+/// what `QuerySource::filter` accepts.
+pub struct Predicate<Lhs, Rhs> {
+    lhs: Lhs,
+    rhs: Rhs,
+    op: &'static str,
+}
+
+impl<Lhs, Rhs> Predicate<Lhs, Rhs> {
+    pub fn new(lhs: Lhs, rhs: Rhs, op: &'static str) -> Self {
+        Predicate { lhs: lhs, rhs: rhs, op: op }
+    }
+}
+
+impl<Lhs, Rhs> Expression for Predicate<Lhs, Rhs>
+where
+    Lhs: Expression,
+    Rhs: Expression<SqlType = Lhs::SqlType>,
+{
+    type SqlType = Bool;
+
+    fn to_sql<'a>(&'a self, binds: &mut Vec<&'a ToSql>) -> String {
+        format!("({} {} {})", self.lhs.to_sql(binds), self.op, self.rhs.to_sql(binds))
+    }
+}
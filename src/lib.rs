@@ -1,3 +1,7 @@
+#[cfg(feature = "chrono")]
+extern crate chrono;
+
+pub mod expression;
 pub mod types;
 
 mod connection;
@@ -9,8 +13,9 @@ mod row;
 mod macros;
 
 pub use result::*;
-pub use query_source::{QuerySource, Queriable, Table, Column, JoinTo};
-pub use connection::Connection;
+pub use query_source::{FilteredSource, QuerySource, Queriable, Insertable, Table, Column, JoinTo};
+pub use connection::{Connection, Executor, RawResult, Transaction};
+pub use row::Cursor;
 
 #[cfg(test)]
 mod test_usage_without_compiler_plugins {
@@ -43,6 +48,13 @@ mod test_usage_without_compiler_plugins {
         title: String,
     }
 
+    #[cfg(feature = "chrono")]
+    #[derive(PartialEq, Eq, Debug)]
+    struct Event {
+        id: i32,
+        created_at: ::chrono::NaiveDateTime,
+    }
+
     // Compiler plugin will automatically invoke this based on schema
     table! {
         users {
@@ -60,6 +72,14 @@ mod test_usage_without_compiler_plugins {
         }
     }
 
+    #[cfg(feature = "chrono")]
+    table! {
+        events {
+            id -> Serial,
+            created_at -> Timestamp,
+        }
+    }
+
     // Compiler plugin will replace this with #[derive(Queriable)]
     queriable! {
         User {
@@ -76,6 +96,15 @@ mod test_usage_without_compiler_plugins {
         }
     }
 
+    // Compiler plugin will replace this with #[derive(Insertable)]. `id`
+    // is `Serial`, so it's left out -- Postgres assigns it.
+    insertable! {
+        UserWithoutId => users {
+            name -> String,
+            age -> Option<i16>,
+        }
+    }
+
     queriable! {
         Post {
             id -> i32,
@@ -84,6 +113,14 @@ mod test_usage_without_compiler_plugins {
         }
     }
 
+    #[cfg(feature = "chrono")]
+    queriable! {
+        Event {
+            id -> i32,
+            created_at -> ::chrono::NaiveDateTime,
+        }
+    }
+
     impl JoinTo<users::table> for posts::table {
         fn join_sql(&self) -> String {
             format!("{} = {}", users::id.name(), posts::user_id.name())
@@ -116,7 +153,7 @@ mod test_usage_without_compiler_plugins {
             (2, "Tess".to_string(), None::<i16>),
          ];
         let actual_data: Vec<_> = connection.query_all(&users::table)
-            .unwrap().collect();
+            .unwrap().collect::<QueryResult<Vec<_>>>().unwrap();
         assert_eq!(expected_data, actual_data);
     }
 
@@ -132,7 +169,7 @@ mod test_usage_without_compiler_plugins {
             User { id: 2, name: "Tess".to_string(), age: None },
          ];
         let actual_users: Vec<_> = connection.query_all(&users::table)
-            .unwrap().collect();
+            .unwrap().collect::<QueryResult<Vec<_>>>().unwrap();
         assert_eq!(expected_users, actual_users);
     }
 
@@ -152,18 +189,112 @@ mod test_usage_without_compiler_plugins {
         // let select_id = users::table.select(posts::id);
         let select_name = users.select(name);
         let ids: Vec<_> = connection.query_all(&select_id)
-            .unwrap().collect();
+            .unwrap().collect::<QueryResult<Vec<_>>>().unwrap();
         let names: Vec<String> = connection.query_all(&select_name)
-            .unwrap().collect();
+            .unwrap().collect::<QueryResult<Vec<_>>>().unwrap();
         // This should fail type checking, and we should add a test to ensure
         // it continues to fail to compile.
         // let names: Vec<String> = connection.query_all(&select_id)
-        //     .unwrap().collect();
+        //     .unwrap().collect::<QueryResult<Vec<_>>>().unwrap();
 
         assert_eq!(vec![1, 2], ids);
         assert_eq!(vec!["Sean".to_string(), "Tess".to_string()], names);
     }
 
+    #[test]
+    fn with_filter() {
+        use self::expression::Expression;
+        use self::users::columns::*;
+        use self::users::table as users;
+
+        let connection = connection();
+        setup_users_table(&connection);
+        connection.execute("INSERT INTO users (name, age) VALUES ('Jim', 30), ('Bob', 40)")
+            .unwrap();
+
+        let source = users.filter(age.gt(35i16)).select(name);
+        // This should fail type checking, and we should add a test to ensure
+        // it continues to fail to compile.
+        // let source = users::table.filter(age.gt("thirty"));
+        let older_names: Vec<String> = connection.query_all(&source)
+            .unwrap().collect::<QueryResult<Vec<_>>>().unwrap();
+
+        assert_eq!(vec!["Bob".to_string()], older_names);
+    }
+
+    #[test]
+    fn with_filter_binds_rather_than_interpolates() {
+        use self::expression::Expression;
+        use self::users::columns::*;
+        use self::users::table as users;
+
+        let connection = connection();
+        setup_users_table(&connection);
+        // A name containing a quote would corrupt a hand-interpolated query,
+        // but `filter` binds it as a parameter instead of formatting it into
+        // the SQL text, so it round-trips unchanged.
+        connection.execute("INSERT INTO users (name, age) VALUES ('O''Brien', 30)")
+            .unwrap();
+
+        let source = users.filter(name.eq("O'Brien")).select(name);
+        let matches: Vec<String> = connection.query_all(&source).unwrap().collect::<QueryResult<Vec<_>>>().unwrap();
+
+        assert_eq!(vec!["O'Brien".to_string()], matches);
+    }
+
+    #[test]
+    fn query_all_params_binds_by_position() {
+        let connection = connection();
+        setup_users_table(&connection);
+        connection.execute("INSERT INTO users (name, age) VALUES ('Sean', 30), ('Tess', 40)")
+            .unwrap();
+
+        let older_names = connection.query_all_params::<types::VarChar, String>(
+            "SELECT name FROM users WHERE age > $1 ORDER BY name",
+            &[&35i16],
+        ).unwrap().collect::<QueryResult<Vec<_>>>().unwrap();
+
+        assert_eq!(vec!["Tess".to_string()], older_names);
+    }
+
+    #[test]
+    fn repeated_queries_reuse_the_prepared_statement() {
+        use self::users::columns::*;
+        use self::users::table as users;
+
+        let connection = connection();
+        setup_users_table(&connection);
+        connection.execute("INSERT INTO users (name, age) VALUES ('Sean', 30)")
+            .unwrap();
+
+        let source = users.select(name);
+        for _ in 0..5 {
+            let names: Vec<String> = connection.query_all(&source).unwrap().collect::<QueryResult<Vec<_>>>().unwrap();
+            assert_eq!(vec!["Sean".to_string()], names);
+        }
+
+        assert_eq!(1, connection.prepare_count());
+    }
+
+    #[test]
+    fn zero_capacity_disables_the_statement_cache() {
+        use self::users::columns::*;
+        use self::users::table as users;
+
+        let connection = connection();
+        setup_users_table(&connection);
+        connection.execute("INSERT INTO users (name, age) VALUES ('Sean', 30)")
+            .unwrap();
+        connection.set_statement_cache_capacity(0).unwrap();
+
+        let source = users.select(name);
+        for _ in 0..3 {
+            let _: Vec<String> = connection.query_all(&source).unwrap().collect::<QueryResult<Vec<_>>>().unwrap();
+        }
+
+        assert_eq!(0, connection.prepare_count());
+    }
+
     #[test]
     fn selecting_multiple_columns() {
         use self::users::columns::*;
@@ -180,7 +311,7 @@ mod test_usage_without_compiler_plugins {
             ("Bob".to_string(), Some(40)),
         ];
         let actual_data: Vec<_> = connection.query_all(&source)
-            .unwrap().collect();
+            .unwrap().collect::<QueryResult<Vec<_>>>().unwrap();
 
         assert_eq!(expected_data, actual_data);
     }
@@ -201,7 +332,7 @@ mod test_usage_without_compiler_plugins {
             UserWithoutId { name: "Bob".to_string(), age:  Some(40) },
         ];
         let actual_data: Vec<_> = connection.query_all(&source)
-            .unwrap().collect();
+            .unwrap().collect::<QueryResult<Vec<_>>>().unwrap();
 
         assert_eq!(expected_data, actual_data);
     }
@@ -247,11 +378,161 @@ mod test_usage_without_compiler_plugins {
 
         let expected_data = vec![(seans_post, sean), (tess_post, tess)];
         let source = posts::table.inner_join(users::table);
-        let actual_data: Vec<_> = connection.query_all(&source).unwrap().collect();
+        let actual_data: Vec<_> = connection.query_all(&source).unwrap().collect::<QueryResult<Vec<_>>>().unwrap();
 
         assert_eq!(expected_data, actual_data);
     }
 
+    #[test]
+    fn inserting_a_struct() {
+        use self::users::columns::*;
+        use self::users::table as users;
+
+        let connection = connection();
+        setup_users_table(&connection);
+
+        let new_user = UserWithoutId { name: "Sean".to_string(), age: Some(30) };
+        let rows_inserted = connection.insert(&users, &new_user).unwrap();
+
+        assert_eq!(1, rows_inserted);
+
+        let source = users.select((name, age));
+        let saved_users: Vec<UserWithoutId> = connection.query_all(&source)
+            .unwrap().collect::<QueryResult<Vec<_>>>().unwrap();
+
+        assert_eq!(vec![new_user], saved_users);
+    }
+
+    #[test]
+    fn inserting_a_struct_with_insert_returning() {
+        let connection = connection();
+        setup_users_table(&connection);
+
+        let new_user = UserWithoutId { name: "Sean".to_string(), age: None };
+        let inserted: Vec<User> = connection.insert_returning(&users::table, &new_user).unwrap();
+
+        assert_eq!(vec![User { id: 1, name: "Sean".to_string(), age: None }], inserted);
+    }
+
+    #[test]
+    fn inserting_several_structs_with_insert_all() {
+        use self::users::columns::*;
+        use self::users::table as users;
+
+        let connection = connection();
+        setup_users_table(&connection);
+
+        let new_users = vec![
+            UserWithoutId { name: "Sean".to_string(), age: Some(30) },
+            UserWithoutId { name: "Tess".to_string(), age: Some(40) },
+        ];
+        let rows_inserted = connection.insert_all(&users, &new_users).unwrap();
+
+        assert_eq!(2, rows_inserted);
+
+        let source = users.select((name, age));
+        let saved_users: Vec<UserWithoutId> = connection.query_all(&source)
+            .unwrap().collect::<QueryResult<Vec<_>>>().unwrap();
+
+        assert_eq!(new_users, saved_users);
+    }
+
+    #[test]
+    fn transaction_commits_when_the_closure_returns_ok() {
+        let connection = connection();
+        setup_users_table(&connection);
+
+        connection.transaction(|tx| {
+            tx.execute("INSERT INTO users (name) VALUES ('Sean')")
+        }).unwrap();
+
+        let select_count = users::table.select_sql::<types::BigInt>("COUNT(*)");
+        let count = connection.query_one::<_, i64>(&select_count).unwrap();
+
+        assert_eq!(Some(1), count);
+    }
+
+    #[test]
+    fn transaction_rolls_back_when_the_closure_returns_err() {
+        let connection = connection();
+        setup_users_table(&connection);
+
+        let result: QueryResult<()> = connection.transaction(|tx| {
+            try!(tx.execute("INSERT INTO users (name) VALUES ('Sean')"));
+            Err(Error::NotFound)
+        });
+
+        assert!(result.is_err());
+
+        let select_count = users::table.select_sql::<types::BigInt>("COUNT(*)");
+        let count = connection.query_one::<_, i64>(&select_count).unwrap();
+
+        assert_eq!(Some(0), count);
+    }
+
+    #[test]
+    fn executor_is_generic_over_connection_and_transaction() {
+        fn count_users<E: Executor>(e: &E) -> i64 {
+            let select_count = users::table.select_sql::<types::BigInt>("COUNT(*)");
+            e.query_one::<_, i64>(&select_count).unwrap().unwrap_or(0)
+        }
+
+        let connection = connection();
+        setup_users_table(&connection);
+        connection.execute("INSERT INTO users (name) VALUES ('Sean')").unwrap();
+
+        assert_eq!(1, count_users(&connection));
+
+        connection.transaction(|tx| {
+            assert_eq!(1, count_users(tx));
+            Ok(())
+        }).unwrap();
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_types_round_trip_through_a_table() {
+        use chrono::NaiveDate;
+
+        let connection = connection();
+        setup_events_table(&connection);
+
+        let created_at = NaiveDate::from_ymd(2021, 3, 4).and_hms(5, 6, 7);
+        connection.execute_params(
+            "INSERT INTO events (created_at) VALUES ($1)",
+            &[&created_at],
+        ).unwrap();
+
+        let events: Vec<Event> = connection.query_all(&events::table)
+            .unwrap().collect::<QueryResult<Vec<_>>>().unwrap();
+
+        assert_eq!(vec![Event { id: 1, created_at: created_at }], events);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_date_and_time_round_trip_through_from_sql_and_to_sql() {
+        use chrono::{NaiveDate, NaiveTime};
+
+        let connection = connection();
+        connection.execute("CREATE TABLE chrono_round_trip (d DATE NOT NULL, t TIME NOT NULL)")
+            .unwrap();
+
+        let d = NaiveDate::from_ymd(2021, 3, 4);
+        let t = NaiveTime::from_hms(5, 6, 7);
+        connection.execute_params(
+            "INSERT INTO chrono_round_trip (d, t) VALUES ($1, $2)",
+            &[&d, &t],
+        ).unwrap();
+
+        let rows = connection.query_all_params::<(types::Date, types::Time), (NaiveDate, NaiveTime)>(
+            "SELECT d, t FROM chrono_round_trip",
+            &[],
+        ).unwrap().collect::<QueryResult<Vec<_>>>().unwrap();
+
+        assert_eq!(vec![(d, t)], rows);
+    }
+
     fn connection() -> Connection {
         let connection_url = ::std::env::var("DATABASE_URL").ok()
             .expect("DATABASE_URL must be set in order to run tests");
@@ -275,4 +556,12 @@ mod test_usage_without_compiler_plugins {
             title VARCHAR NOT NULL
         )").unwrap();
     }
+
+    #[cfg(feature = "chrono")]
+    fn setup_events_table(connection: &Connection) {
+        connection.execute("CREATE TABLE events (
+            id SERIAL PRIMARY KEY,
+            created_at TIMESTAMP NOT NULL
+        )").unwrap();
+    }
 }
@@ -0,0 +1,176 @@
+/// Declares a table's columns and their `NativeSqlType`s. Stands in for
+/// the compiler plugin that will eventually generate this from the actual
+/// schema -- see the comments in `test_usage_without_compiler_plugins`.
+#[macro_export]
+macro_rules! table {
+    ($table_name:ident { $($column_name:ident -> $Type:ty,)+ }) => {
+        pub mod $table_name {
+            #![allow(non_camel_case_types)]
+            #[allow(unused_imports)]
+            use $crate::types::*;
+            use $crate::{QuerySource, Table};
+
+            pub type SqlType = ($($Type,)+);
+
+            #[derive(Debug, Clone, Copy, Default)]
+            pub struct table;
+
+            impl QuerySource for table {
+                type SqlType = self::SqlType;
+
+                fn select_clause(&self) -> String {
+                    "*".to_string()
+                }
+
+                fn from_clause(&self) -> String {
+                    stringify!($table_name).to_string()
+                }
+            }
+
+            impl Table for table {
+                fn name(&self) -> &'static str {
+                    stringify!($table_name)
+                }
+            }
+
+            pub use self::columns::*;
+
+            pub mod columns {
+                #![allow(non_camel_case_types)]
+                #[allow(unused_imports)]
+                use $crate::types::*;
+                use $crate::Column;
+                use $crate::expression::Expression;
+                use $crate::query_source::Selectable;
+
+                $(
+                    #[derive(Debug, Clone, Copy, Default)]
+                    pub struct $column_name;
+
+                    impl Column for $column_name {
+                        type Table = super::table;
+                        type SqlType = $Type;
+
+                        fn name(&self) -> String {
+                            concat!(stringify!($table_name), ".", stringify!($column_name)).to_string()
+                        }
+                    }
+
+                    impl Selectable for $column_name {
+                        type SqlType = $Type;
+
+                        fn to_sql(&self) -> String {
+                            Column::name(self)
+                        }
+                    }
+
+                    impl Expression for $column_name {
+                        type SqlType = $Type;
+
+                        fn to_sql<'a>(&'a self, _binds: &mut Vec<&'a ToSql>) -> String {
+                            Column::name(self)
+                        }
+                    }
+                )+
+            }
+        }
+    }
+}
+
+/// Declares how a struct's fields map onto a table's columns for
+/// `Connection::insert`. Stands in for `#[derive(Insertable)]`. `$field`
+/// must name both the struct field and the matching column in
+/// `$table_mod`'s `columns` module -- that's what lets a stray column
+/// (one the table doesn't have) fail to compile, the inverse of
+/// `queriable!`. `$Type` must additionally be the same Rust type `queriable!`
+/// would decode that column into, so e.g. `name -> i32` against a `VarChar`
+/// column fails to compile too.
+#[macro_export]
+macro_rules! insertable {
+    ($Struct:ident => $table_mod:ident { $($field:ident -> $Type:ty,)+ }) => {
+        impl $crate::Insertable<$table_mod::table> for $Struct {
+            fn column_names() -> Vec<&'static str> {
+                // Naming a nonexistent `$table_mod::columns::$field` is a
+                // compile error, so this rejects a field that isn't
+                // actually one of the table's columns.
+                #[allow(dead_code, unused_variables)]
+                fn assert_columns_exist() -> ($($table_mod::columns::$field,)+) {
+                    Default::default()
+                }
+
+                // `$Type: FromSql<column's SqlType>` is the same bound
+                // `queriable!` relies on to decode a column back into a
+                // Rust value, so reusing it here rejects a `$Type` that
+                // doesn't actually match what the column holds.
+                #[allow(dead_code)]
+                fn assert_types_match() {
+                    fn assert<T, ST>()
+                    where
+                        T: $crate::types::FromSql<ST>,
+                        ST: $crate::types::NativeSqlType,
+                    {}
+                    $(
+                        assert::<$Type, <$table_mod::columns::$field as $crate::Column>::SqlType>();
+                    )+
+                }
+
+                vec![$(stringify!($field)),+]
+            }
+
+            fn values<'a>(&'a self) -> Vec<&'a $crate::types::ToSql> {
+                vec![$(&self.$field as &$crate::types::ToSql),+]
+            }
+        }
+    };
+}
+
+/// Declares the mapping between a struct's fields and the Rust types a row
+/// should decode into. Stands in for `#[derive(Queriable)]`.
+#[macro_export]
+macro_rules! queriable {
+    ($Struct:ident { $f1:ident -> $T1:ty, }) => {
+        impl<S1> $crate::Queriable<(S1,)> for $Struct
+        where
+            $T1: $crate::row::FromSqlRow<S1>,
+            S1: $crate::types::NativeSqlType,
+        {
+            type Row = ($T1,);
+
+            fn build(row: Self::Row) -> Self {
+                $Struct { $f1: row.0 }
+            }
+        }
+    };
+    ($Struct:ident { $f1:ident -> $T1:ty, $f2:ident -> $T2:ty, }) => {
+        impl<S1, S2> $crate::Queriable<(S1, S2)> for $Struct
+        where
+            $T1: $crate::row::FromSqlRow<S1>,
+            S1: $crate::types::NativeSqlType,
+            $T2: $crate::row::FromSqlRow<S2>,
+            S2: $crate::types::NativeSqlType,
+        {
+            type Row = ($T1, $T2);
+
+            fn build(row: Self::Row) -> Self {
+                $Struct { $f1: row.0, $f2: row.1 }
+            }
+        }
+    };
+    ($Struct:ident { $f1:ident -> $T1:ty, $f2:ident -> $T2:ty, $f3:ident -> $T3:ty, }) => {
+        impl<S1, S2, S3> $crate::Queriable<(S1, S2, S3)> for $Struct
+        where
+            $T1: $crate::row::FromSqlRow<S1>,
+            S1: $crate::types::NativeSqlType,
+            $T2: $crate::row::FromSqlRow<S2>,
+            S2: $crate::types::NativeSqlType,
+            $T3: $crate::row::FromSqlRow<S3>,
+            S3: $crate::types::NativeSqlType,
+        {
+            type Row = ($T1, $T2, $T3);
+
+            fn build(row: Self::Row) -> Self {
+                $Struct { $f1: row.0, $f2: row.1, $f3: row.2 }
+            }
+        }
+    };
+}
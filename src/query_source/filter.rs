@@ -0,0 +1,43 @@
+use expression::Expression;
+use types::ToSql;
+use super::QuerySource;
+
+/// A `QuerySource` produced by `QuerySource::filter`. Forwards everything
+/// about the inner source except the `WHERE` clause, which is the
+/// predicate's SQL, `AND`ed onto the inner source's own `WHERE` clause if
+/// it has one.
+pub struct FilteredSource<Source, Predicate> {
+    source: Source,
+    predicate: Predicate,
+}
+
+impl<Source, Predicate> FilteredSource<Source, Predicate> {
+    pub fn new(source: Source, predicate: Predicate) -> Self {
+        FilteredSource { source: source, predicate: predicate }
+    }
+}
+
+impl<Source, Predicate> QuerySource for FilteredSource<Source, Predicate>
+where
+    Source: QuerySource,
+    Predicate: Expression,
+{
+    type SqlType = Source::SqlType;
+
+    fn select_clause(&self) -> String {
+        self.source.select_clause()
+    }
+
+    fn from_clause(&self) -> String {
+        self.source.from_clause()
+    }
+
+    fn where_clause<'a>(&'a self, binds: &mut Vec<&'a ToSql>) -> Option<String> {
+        let existing = self.source.where_clause(binds);
+        let predicate_sql = self.predicate.to_sql(binds);
+        match existing {
+            Some(existing) => Some(format!("{} AND {}", existing, predicate_sql)),
+            None => Some(predicate_sql),
+        }
+    }
+}
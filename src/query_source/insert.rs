@@ -0,0 +1,15 @@
+use types::ToSql;
+use super::Table;
+
+/// A Rust value that can supply the column list and bound parameter values
+/// for an `INSERT INTO` statement against `T`, generated by the
+/// `insertable!` macro. Mirrors `Queriable` for the opposite direction:
+/// `Queriable` turns a decoded row into a struct, `Insertable` turns a
+/// struct into the column/value pairs of a row to write.
+pub trait Insertable<T: Table> {
+    /// The bare (unqualified) names of the columns this record supplies,
+    /// in the same order `values` returns their bound parameters.
+    fn column_names() -> Vec<&'static str>;
+
+    fn values<'a>(&'a self) -> Vec<&'a ToSql>;
+}
@@ -0,0 +1,43 @@
+use super::{QuerySource, Table};
+
+/// Implemented on the "owning" side of a relationship (e.g. `posts` for a
+/// `belongs_to users`), describing how to join back to `T`. `inner_join`
+/// requires `Self: JoinTo<T>` so an `inner_join` between unrelated tables
+/// is a compile error rather than a runtime one.
+pub trait JoinTo<T> {
+    fn join_sql(&self) -> String;
+}
+
+/// A `QuerySource` produced by `Table::inner_join`. Rows come back as
+/// `(Left, Right)`, which is why `Queriable` has tuple impls.
+pub struct InnerJoinSource<Left, Right> {
+    left: Left,
+    right: Right,
+}
+
+impl<Left, Right> InnerJoinSource<Left, Right> {
+    pub fn new(left: Left, right: Right) -> Self {
+        InnerJoinSource { left: left, right: right }
+    }
+}
+
+impl<Left, Right> QuerySource for InnerJoinSource<Left, Right>
+where
+    Left: Table + JoinTo<Right>,
+    Right: Table,
+{
+    type SqlType = (Left::SqlType, Right::SqlType);
+
+    fn select_clause(&self) -> String {
+        "*".to_string()
+    }
+
+    fn from_clause(&self) -> String {
+        format!(
+            "{} INNER JOIN {} ON {}",
+            self.left.from_clause(),
+            self.right.from_clause(),
+            self.left.join_sql(),
+        )
+    }
+}
@@ -0,0 +1,106 @@
+use expression::Expression;
+use row::FromSqlRow;
+use types::{Bool, NativeSqlType, ToSql};
+
+mod filter;
+mod insert;
+mod joins;
+mod select;
+mod queriable_impls;
+
+pub use self::filter::FilteredSource;
+pub use self::insert::Insertable;
+pub use self::joins::{InnerJoinSource, JoinTo};
+pub use self::select::{Selectable, SelectStatement, SqlLiteralSource};
+
+/// A source of rows that can be turned into a `SELECT` statement. Every
+/// source knows its own `SqlType`, the tuple of `NativeSqlType`s its
+/// columns decode to, so `Connection::query_all`/`query_one` can require
+/// `T: Queriable<Self::SqlType>` and reject mismatched result types at
+/// compile time.
+pub trait QuerySource {
+    type SqlType: NativeSqlType;
+
+    fn select_clause(&self) -> String;
+    fn from_clause(&self) -> String;
+
+    /// Renders the `WHERE` clause's predicate, if any, pushing its literal
+    /// operands onto `binds` as placeholders rather than formatting them
+    /// into the returned string.
+    fn where_clause<'a>(&'a self, _binds: &mut Vec<&'a ToSql>) -> Option<String> {
+        None
+    }
+
+    fn to_sql<'a>(&'a self, binds: &mut Vec<&'a ToSql>) -> String {
+        let mut sql = format!("SELECT {} FROM {}", self.select_clause(), self.from_clause());
+        if let Some(predicate_sql) = self.where_clause(binds) {
+            sql.push_str(" WHERE ");
+            sql.push_str(&predicate_sql);
+        }
+        sql
+    }
+
+    /// Restricts the rows this source produces to those matching
+    /// `predicate`, the statically checked equivalent of hand-writing a
+    /// `WHERE` clause.
+    fn filter<P>(self, predicate: P) -> FilteredSource<Self, P>
+    where
+        Self: Sized,
+        P: Expression<SqlType = Bool>,
+    {
+        FilteredSource::new(self, predicate)
+    }
+
+    /// Restricts the columns this source returns to `selection`, a single
+    /// `Column` or a tuple of them.
+    fn select<S: Selectable>(self, selection: S) -> SelectStatement<Self, S>
+    where
+        Self: Sized,
+    {
+        SelectStatement::new(self, selection)
+    }
+
+    /// Like `select`, for the cases a typed `selection` can't express yet
+    /// (e.g. `COUNT(*)`). The caller is trusted to supply an `ST` that
+    /// matches what `sql` actually returns.
+    fn select_sql<ST: NativeSqlType>(self, sql: &str) -> SqlLiteralSource<Self, ST>
+    where
+        Self: Sized,
+    {
+        SqlLiteralSource::new(self, sql.to_string())
+    }
+}
+
+/// A Rust type that a row of `SqlType` can be deserialized into.
+/// Implemented by hand for primitives and tuples, and generated by the
+/// `queriable!` macro for user structs.
+pub trait Queriable<ST: NativeSqlType> {
+    type Row: FromSqlRow<ST>;
+
+    fn build(row: Self::Row) -> Self;
+}
+
+/// A single column belonging to a `Table`. `name` is always qualified by
+/// the table it belongs to (e.g. `"users.id"`), so it stays unambiguous
+/// once used in a join.
+pub trait Column {
+    type Table: Table;
+    type SqlType: NativeSqlType;
+
+    fn name(&self) -> String;
+}
+
+/// A database table that can be queried directly (`SELECT * FROM ...`), or
+/// used as the starting point for `select`, `select_sql`, `filter`, and
+/// `inner_join`.
+pub trait Table: QuerySource + Sized {
+    fn name(&self) -> &'static str;
+
+    fn inner_join<T>(self, other: T) -> InnerJoinSource<Self, T>
+    where
+        Self: JoinTo<T>,
+        T: Table,
+    {
+        InnerJoinSource::new(self, other)
+    }
+}
@@ -0,0 +1,58 @@
+use types::{BigInt, Bool, Integer, NativeSqlType, Nullable, Serial, SmallInt, VarChar};
+use super::Queriable;
+
+macro_rules! primitive_queriable {
+    ($ty:ty, $sql_type:ty) => {
+        impl Queriable<$sql_type> for $ty {
+            type Row = $ty;
+
+            fn build(row: Self::Row) -> Self {
+                row
+            }
+        }
+    }
+}
+
+primitive_queriable!(i16, SmallInt);
+primitive_queriable!(i32, Integer);
+primitive_queriable!(i32, Serial);
+primitive_queriable!(i64, BigInt);
+primitive_queriable!(bool, Bool);
+primitive_queriable!(String, VarChar);
+
+impl<T, ST> Queriable<Nullable<ST>> for Option<T>
+where
+    T: ::types::FromSql<ST>,
+    ST: ::types::NativeSqlType,
+    Option<T>: ::row::FromSqlRow<Nullable<ST>>,
+{
+    type Row = Option<T>;
+
+    fn build(row: Self::Row) -> Self {
+        row
+    }
+}
+
+// Bound on `FromSqlRow` rather than `Queriable` so this only covers tuples
+// of raw decodable values, e.g. `(i32, String, Option<i16>)` for a
+// `users.select(...)` with no `queriable!` struct. A tuple of `queriable!`
+// structs (e.g. the `(Post, User)` an `inner_join` produces) is *not*
+// `FromSqlRow`, so it needs its own hand-written `Queriable` impl -- the
+// same way the compiler plugin would generate one for each `belongs_to`.
+macro_rules! queriable_tuple {
+    ($($T:ident: $ST:ident),+) => {
+        impl<$($T, $ST),+> Queriable<($($ST,)+)> for ($($T,)+)
+        where $($T: ::row::FromSqlRow<$ST>, $ST: NativeSqlType),+
+        {
+            type Row = ($($T,)+);
+
+            fn build(row: Self::Row) -> Self {
+                row
+            }
+        }
+    }
+}
+
+queriable_tuple!(A: SA, B: SB);
+queriable_tuple!(A: SA, B: SB, C: SC);
+queriable_tuple!(A: SA, B: SB, C: SC, D: SD);
@@ -0,0 +1,103 @@
+use std::marker::PhantomData;
+
+use types::{NativeSqlType, ToSql};
+use super::QuerySource;
+
+/// Something that can appear in the column list of a `SELECT`: a single
+/// `Column`, or a tuple of them. `QuerySource::select` is generic over this
+/// so the result type it produces always matches the columns that were
+/// actually selected.
+pub trait Selectable {
+    type SqlType: NativeSqlType;
+
+    fn to_sql(&self) -> String;
+}
+
+/// A `QuerySource` produced by `QuerySource::select`. Delegates `FROM` and
+/// `WHERE` to the underlying source, and overrides the column list with
+/// `selection`.
+pub struct SelectStatement<Source, Selection> {
+    source: Source,
+    selection: Selection,
+}
+
+impl<Source, Selection> SelectStatement<Source, Selection> {
+    pub fn new(source: Source, selection: Selection) -> Self {
+        SelectStatement { source: source, selection: selection }
+    }
+}
+
+impl<Source, Selection> QuerySource for SelectStatement<Source, Selection>
+where
+    Source: QuerySource,
+    Selection: Selectable,
+{
+    type SqlType = Selection::SqlType;
+
+    fn select_clause(&self) -> String {
+        self.selection.to_sql()
+    }
+
+    fn from_clause(&self) -> String {
+        self.source.from_clause()
+    }
+
+    fn where_clause<'a>(&'a self, binds: &mut Vec<&'a ToSql>) -> Option<String> {
+        self.source.where_clause(binds)
+    }
+}
+
+/// A `QuerySource` produced by `QuerySource::select_sql`, for the cases a typed
+/// `select` can't express (aggregates like `COUNT(*)`). The caller is
+/// trusted to supply a `SqlType` that matches what the SQL actually
+/// returns.
+pub struct SqlLiteralSource<Source, ST> {
+    source: Source,
+    sql: String,
+    _marker: PhantomData<ST>,
+}
+
+impl<Source, ST> SqlLiteralSource<Source, ST> {
+    pub fn new(source: Source, sql: String) -> Self {
+        SqlLiteralSource { source: source, sql: sql, _marker: PhantomData }
+    }
+}
+
+impl<Source, ST> QuerySource for SqlLiteralSource<Source, ST>
+where
+    Source: QuerySource,
+    ST: NativeSqlType,
+{
+    type SqlType = ST;
+
+    fn select_clause(&self) -> String {
+        self.sql.clone()
+    }
+
+    fn from_clause(&self) -> String {
+        self.source.from_clause()
+    }
+
+    fn where_clause<'a>(&'a self, binds: &mut Vec<&'a ToSql>) -> Option<String> {
+        self.source.where_clause(binds)
+    }
+}
+
+macro_rules! selectable_tuple {
+    ($($T:ident),+) => {
+        impl<$($T: Selectable),+> Selectable for ($($T,)+) {
+            type SqlType = ($($T::SqlType,)+);
+
+            #[allow(non_snake_case)]
+            fn to_sql(&self) -> String {
+                let ($(ref $T,)+) = *self;
+                let columns: Vec<String> = vec![$($T.to_sql()),+];
+                columns.join(", ")
+            }
+        }
+    }
+}
+
+selectable_tuple!(A, B);
+selectable_tuple!(A, B, C);
+selectable_tuple!(A, B, C, D);
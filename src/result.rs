@@ -0,0 +1,59 @@
+use std::error::Error as StdError;
+use std::fmt;
+
+pub type QueryResult<T> = Result<T, Error>;
+pub type ConnectionResult<T> = Result<T, ConnectionError>;
+
+/// The error type returned by any operation that talks to the database,
+/// either while running the query itself or while decoding the rows it
+/// returned.
+#[derive(Debug)]
+pub enum Error {
+    NotFound,
+    DatabaseError(String),
+    DeserializationError(Box<StdError>),
+    QueryBuilderError(Box<StdError>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::NotFound => write!(f, "NotFound"),
+            Error::DatabaseError(ref s) => write!(f, "{}", s),
+            Error::DeserializationError(ref e) => write!(f, "{}", e),
+            Error::QueryBuilderError(ref e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::NotFound => "NotFound",
+            Error::DatabaseError(ref s) => s,
+            Error::DeserializationError(_) => "Error deserializing a row",
+            Error::QueryBuilderError(_) => "Error building a query",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConnectionError {
+    BadConnection(String),
+}
+
+impl fmt::Display for ConnectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ConnectionError::BadConnection(ref s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl StdError for ConnectionError {
+    fn description(&self) -> &str {
+        match *self {
+            ConnectionError::BadConnection(ref s) => s,
+        }
+    }
+}
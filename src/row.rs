@@ -0,0 +1,158 @@
+use query_source::Queriable;
+use result::{Error, QueryResult};
+use types::{BigInt, Bool, Integer, NativeSqlType, Nullable, Serial, SmallInt, VarChar};
+use types::FromSql;
+
+/// The raw column values for a single row of a result set, in the order
+/// the columns were selected.
+pub struct RawRow(Vec<Option<Vec<u8>>>);
+
+impl RawRow {
+    pub fn new(columns: Vec<Option<Vec<u8>>>) -> Self {
+        RawRow(columns)
+    }
+}
+
+/// A cursor over the columns of a single `RawRow`. `Queriable::Row` values
+/// are assembled by repeatedly calling `take()`, so nothing needs to know
+/// the row's arity up front -- a tuple impl of `FromSqlRow` just calls
+/// `take()` once per element, left to right.
+pub struct Row<'a> {
+    data: &'a RawRow,
+    column_index: ::std::cell::Cell<usize>,
+}
+
+impl<'a> Row<'a> {
+    pub fn new(data: &'a RawRow) -> Self {
+        Row { data: data, column_index: ::std::cell::Cell::new(0) }
+    }
+
+    pub fn take(&self) -> Option<&'a [u8]> {
+        let index = self.column_index.get();
+        self.column_index.set(index + 1);
+        self.data.0.get(index).and_then(|c| c.as_ref()).map(|v| &v[..])
+    }
+}
+
+/// Builds a `Self` out of however many columns of a `Row` its `SqlType`
+/// requires, advancing the row's cursor as it goes.
+pub trait FromSqlRow<A>: Sized {
+    fn build_from_row(row: &Row) -> Result<Self, Error>;
+}
+
+/// Anything that can hand a `Cursor` its rows one at a time, without
+/// requiring the whole result set to already be in memory. Implemented by
+/// `connection::raw::RawResult`; kept separate from that type so `row`
+/// doesn't need to know about libpq.
+pub trait RowSource {
+    fn row_count(&self) -> usize;
+    fn get_row(&self, index: usize) -> RawRow;
+}
+
+/// A lazy, fallible iterator over the rows of a query's result set.
+/// `Connection::query_all` returns one of these instead of an already
+/// collected `Vec`, so decoding a row only happens (and can only fail) as
+/// the caller actually asks for it -- `.collect::<QueryResult<Vec<T>>>()`
+/// gets the eager all-or-nothing behavior back, and `first()`/`nth()` stop
+/// fetching as soon as they have what they need.
+pub struct Cursor<R, ST, T> {
+    source: R,
+    row_count: usize,
+    next_index: usize,
+    _marker: ::std::marker::PhantomData<(ST, T)>,
+}
+
+impl<R: RowSource, ST, T> Cursor<R, ST, T> {
+    pub fn new(source: R) -> Self {
+        let row_count = source.row_count();
+        Cursor {
+            source: source,
+            row_count: row_count,
+            next_index: 0,
+            _marker: ::std::marker::PhantomData,
+        }
+    }
+}
+
+impl<R: RowSource, ST: NativeSqlType, T: Queriable<ST>> Cursor<R, ST, T> {
+    /// Like `Iterator::next`, but reads better at a call site that only
+    /// wants the first row (e.g. a `query_one`-style lookup).
+    pub fn first(mut self) -> Option<QueryResult<T>> {
+        self.next()
+    }
+
+    /// Decodes and returns the `n`th row (0-indexed), without decoding the
+    /// rows before it.
+    pub fn nth(&mut self, n: usize) -> Option<QueryResult<T>> {
+        self.next_index += n;
+        Iterator::next(self)
+    }
+}
+
+impl<R: RowSource, ST: NativeSqlType, T: Queriable<ST>> Iterator for Cursor<R, ST, T> {
+    type Item = QueryResult<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_index >= self.row_count {
+            return None;
+        }
+        let raw_row = self.source.get_row(self.next_index);
+        self.next_index += 1;
+        let row = Row::new(&raw_row);
+        Some(<T::Row as FromSqlRow<ST>>::build_from_row(&row).map(T::build))
+    }
+}
+
+macro_rules! primitive_from_sql_row {
+    ($ty:ty, $sql_type:ty) => {
+        impl FromSqlRow<$sql_type> for $ty {
+            fn build_from_row(row: &Row) -> Result<Self, Error> {
+                <$ty as FromSql<$sql_type>>::from_sql(row.take())
+                    .map_err(Error::DeserializationError)
+            }
+        }
+    }
+}
+
+primitive_from_sql_row!(i16, SmallInt);
+primitive_from_sql_row!(i32, Integer);
+primitive_from_sql_row!(i32, Serial);
+primitive_from_sql_row!(i64, BigInt);
+primitive_from_sql_row!(bool, Bool);
+primitive_from_sql_row!(String, VarChar);
+
+#[cfg(feature = "chrono")]
+primitive_from_sql_row!(::chrono::NaiveDateTime, ::types::Timestamp);
+#[cfg(feature = "chrono")]
+primitive_from_sql_row!(::chrono::NaiveDate, ::types::Date);
+#[cfg(feature = "chrono")]
+primitive_from_sql_row!(::chrono::NaiveTime, ::types::Time);
+
+impl<T, ST> FromSqlRow<Nullable<ST>> for Option<T>
+where
+    T: FromSql<ST>,
+    ST: ::types::NativeSqlType,
+{
+    fn build_from_row(row: &Row) -> Result<Self, Error> {
+        Option::<T>::from_sql(row.take()).map_err(Error::DeserializationError)
+    }
+}
+
+macro_rules! tuple_from_sql_row {
+    ($($T:ident: $ST:ident),+) => {
+        impl<$($T, $ST),+> FromSqlRow<($($ST,)+)> for ($($T,)+)
+        where $($T: FromSqlRow<$ST>),+
+        {
+            #[allow(non_snake_case)]
+            fn build_from_row(row: &Row) -> Result<Self, Error> {
+                $(let $T = try!($T::build_from_row(row));)+
+                Ok(($($T,)+))
+            }
+        }
+    }
+}
+
+tuple_from_sql_row!(A: SA);
+tuple_from_sql_row!(A: SA, B: SB);
+tuple_from_sql_row!(A: SA, B: SB, C: SC);
+tuple_from_sql_row!(A: SA, B: SB, C: SC, D: SD);
@@ -0,0 +1,65 @@
+use std::error::Error;
+use std::str;
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+use super::{Date, FromSql, Time, Timestamp, ToSql, ToSqlValue};
+
+// Queries are run through `PQexec`/`PQexecParams` with a text result
+// format (see `connection::raw`), never Postgres' binary protocol, so
+// these decode the same space-separated text representation `psql` would
+// print rather than the binary microseconds/days-since-2000-01-01 layout.
+const TIMESTAMP_FORMAT: &'static str = "%Y-%m-%d %H:%M:%S%.f";
+const DATE_FORMAT: &'static str = "%Y-%m-%d";
+const TIME_FORMAT: &'static str = "%H:%M:%S%.f";
+
+macro_rules! not_none {
+    ($bytes:expr) => {
+        match $bytes {
+            Some(bytes) => bytes,
+            None => return Err(Box::new(super::UnexpectedNullError)),
+        }
+    }
+}
+
+impl FromSql<Timestamp> for NaiveDateTime {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let bytes = not_none!(bytes);
+        let s = try!(str::from_utf8(bytes).map_err(|e| Box::new(e) as Box<Error>));
+        NaiveDateTime::parse_from_str(s, TIMESTAMP_FORMAT).map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+impl FromSql<Date> for NaiveDate {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let bytes = not_none!(bytes);
+        let s = try!(str::from_utf8(bytes).map_err(|e| Box::new(e) as Box<Error>));
+        NaiveDate::parse_from_str(s, DATE_FORMAT).map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+impl FromSql<Time> for NaiveTime {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let bytes = not_none!(bytes);
+        let s = try!(str::from_utf8(bytes).map_err(|e| Box::new(e) as Box<Error>));
+        NaiveTime::parse_from_str(s, TIME_FORMAT).map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+impl ToSql for NaiveDateTime {
+    fn to_sql(&self) -> ToSqlValue {
+        ToSqlValue::Owned(self.format(TIMESTAMP_FORMAT).to_string().into_bytes())
+    }
+}
+
+impl ToSql for NaiveDate {
+    fn to_sql(&self) -> ToSqlValue {
+        ToSqlValue::Owned(self.format(DATE_FORMAT).to_string().into_bytes())
+    }
+}
+
+impl ToSql for NaiveTime {
+    fn to_sql(&self) -> ToSqlValue {
+        ToSqlValue::Owned(self.format(TIME_FORMAT).to_string().into_bytes())
+    }
+}
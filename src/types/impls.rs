@@ -0,0 +1,120 @@
+use std::error::Error;
+use std::fmt;
+use std::str;
+
+use super::{BigInt, Bool, FromSql, Integer, NativeSqlType, Nullable, SmallInt, Serial, ToSql,
+            ToSqlValue, VarChar};
+
+#[derive(Debug, Clone, Copy)]
+pub struct UnexpectedNullError;
+
+impl fmt::Display for UnexpectedNullError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unexpected NULL for a non-nullable column")
+    }
+}
+
+impl Error for UnexpectedNullError {
+    fn description(&self) -> &str {
+        "Unexpected NULL for a non-nullable column"
+    }
+}
+
+macro_rules! not_none {
+    ($bytes:expr) => {
+        match $bytes {
+            Some(bytes) => bytes,
+            None => return Err(Box::new(UnexpectedNullError)),
+        }
+    }
+}
+
+macro_rules! int_from_sql {
+    ($ty:ty, $native:ty) => {
+        impl FromSql<$native> for $ty {
+            fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+                let bytes = not_none!(bytes);
+                str::from_utf8(bytes)
+                    .map_err(|e| Box::new(e) as Box<Error>)
+                    .and_then(|s| s.parse().map_err(|e: ::std::num::ParseIntError| {
+                        Box::new(e) as Box<Error>
+                    }))
+            }
+        }
+    }
+}
+
+int_from_sql!(i16, SmallInt);
+int_from_sql!(i32, Integer);
+int_from_sql!(i32, Serial);
+int_from_sql!(i64, BigInt);
+
+impl FromSql<Bool> for bool {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let bytes = not_none!(bytes);
+        Ok(bytes == b"t")
+    }
+}
+
+impl FromSql<VarChar> for String {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        let bytes = not_none!(bytes);
+        str::from_utf8(bytes)
+            .map(|s| s.to_string())
+            .map_err(|e| Box::new(e) as Box<Error>)
+    }
+}
+
+impl<T, ST> FromSql<Nullable<ST>> for Option<T>
+where
+    ST: NativeSqlType,
+    T: FromSql<ST>,
+{
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>> {
+        match bytes {
+            Some(bytes) => T::from_sql(Some(bytes)).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+macro_rules! int_to_sql {
+    ($ty:ty) => {
+        impl ToSql for $ty {
+            fn to_sql(&self) -> ToSqlValue {
+                ToSqlValue::Owned(self.to_string().into_bytes())
+            }
+        }
+    }
+}
+
+int_to_sql!(i16);
+int_to_sql!(i32);
+int_to_sql!(i64);
+
+impl ToSql for bool {
+    fn to_sql(&self) -> ToSqlValue {
+        ToSqlValue::Borrowed(if *self { b"t" } else { b"f" })
+    }
+}
+
+impl ToSql for String {
+    fn to_sql(&self) -> ToSqlValue {
+        ToSqlValue::Borrowed(self.as_bytes())
+    }
+}
+
+impl<'a> ToSql for &'a str {
+    fn to_sql(&self) -> ToSqlValue {
+        ToSqlValue::Borrowed(self.as_bytes())
+    }
+}
+
+impl<T: ToSql> ToSql for Option<T> {
+    fn to_sql(&self) -> ToSqlValue {
+        match *self {
+            Some(ref value) => value.to_sql(),
+            None => ToSqlValue::Null,
+        }
+    }
+}
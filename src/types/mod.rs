@@ -0,0 +1,102 @@
+//! Mappings between Rust types and the native SQL types used by the
+//! `table!` macro (`Integer`, `VarChar`, ...). These types never exist as
+//! values -- they're purely type-level tags used to pick the right
+//! `FromSql`/`ToSql` implementation for a given column.
+use std::error::Error;
+use std::marker::PhantomData;
+
+mod impls;
+#[cfg(feature = "chrono")]
+mod chrono_impls;
+
+pub use self::impls::UnexpectedNullError;
+
+/// A marker trait for a type which represents a native SQL type, e.g.
+/// `Integer` or `VarChar`.
+pub trait NativeSqlType {}
+
+/// Indicates that a given Rust type can be constructed from a value of the
+/// given `NativeSqlType`. `bytes` is `None` when the column is `NULL`, and
+/// otherwise contains Postgres' text representation of the value (queries
+/// are run through `PQexec`, `PQexecParams` or `PQexecPrepared`, always
+/// asking for results in text format).
+pub trait FromSql<A: NativeSqlType>: Sized {
+    fn from_sql(bytes: Option<&[u8]>) -> Result<Self, Box<Error>>;
+}
+
+/// The wire encoding of a single bound parameter, as produced by `ToSql`.
+/// Borrows out of the value being encoded when possible, so binding a
+/// `String`/`&str` parameter doesn't need to allocate.
+pub enum ToSqlValue<'a> {
+    Borrowed(&'a [u8]),
+    Owned(Vec<u8>),
+    Null,
+}
+
+impl<'a> ToSqlValue<'a> {
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match *self {
+            ToSqlValue::Borrowed(bytes) => Some(bytes),
+            ToSqlValue::Owned(ref bytes) => Some(bytes),
+            ToSqlValue::Null => None,
+        }
+    }
+}
+
+/// The inverse of `FromSql`: encodes a Rust value as the text Postgres
+/// expects for a bound parameter, for use with
+/// `Connection::execute_params`/`query_all_params` (and the query builder's
+/// own `filter`, which binds its literal operands the same way rather than
+/// interpolating them into the SQL text).
+pub trait ToSql {
+    fn to_sql(&self) -> ToSqlValue;
+}
+
+macro_rules! native_sql_type {
+    ($name:ident) => {
+        #[derive(Debug, Clone, Copy, Default)]
+        pub struct $name;
+
+        impl NativeSqlType for $name {}
+    }
+}
+
+native_sql_type!(Bool);
+native_sql_type!(SmallInt);
+native_sql_type!(Integer);
+native_sql_type!(BigInt);
+native_sql_type!(VarChar);
+// `Serial` decodes just like `Integer`, it only differs in how the column
+// is declared on the Postgres side (`SERIAL` vs `INTEGER`).
+native_sql_type!(Serial);
+
+// These three only exist with the `chrono` feature enabled, since they're
+// the only `NativeSqlType`s with a Rust-side representation that isn't
+// already in `std` -- every other type here decodes into a primitive or
+// `String`, which don't need an extra dependency to be usable.
+#[cfg(feature = "chrono")]
+native_sql_type!(Timestamp);
+#[cfg(feature = "chrono")]
+native_sql_type!(Date);
+#[cfg(feature = "chrono")]
+native_sql_type!(Time);
+
+/// The `NativeSqlType` of a nullable column. Wraps the underlying type so
+/// `FromSql`/`ToSql` can be implemented generically for `Option<T>`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Nullable<T: NativeSqlType>(PhantomData<T>);
+
+impl<T: NativeSqlType> NativeSqlType for Nullable<T> {}
+
+// A `QuerySource` selecting several columns at once (or joining two tables)
+// has a `SqlType` that's a tuple of its parts' `SqlType`s, so tuples of
+// `NativeSqlType` need to count as one too.
+macro_rules! native_sql_type_tuple {
+    ($($T:ident),+) => {
+        impl<$($T: NativeSqlType),+> NativeSqlType for ($($T,)+) {}
+    }
+}
+
+native_sql_type_tuple!(A, B);
+native_sql_type_tuple!(A, B, C);
+native_sql_type_tuple!(A, B, C, D);